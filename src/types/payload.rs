@@ -1,7 +1,12 @@
 //! Basic binary and string payload extractors.
 
+#[cfg(feature = "compress")]
+use std::{cell::Cell, rc::Rc};
 use std::{
+    fs::File,
     future::Future,
+    io::{self, Seek, SeekFrom, Write},
+    path::PathBuf,
     pin::Pin,
     str,
     task::{Context, Poll},
@@ -107,6 +112,10 @@ impl FromRequest for Bytes {
 
         let limit = cfg.limit;
         let fut = HttpMessageBody::new(req, payload).limit(limit);
+        #[cfg(feature = "compress")]
+        let fut = fut
+            .max_decompress_ratio(cfg.max_decompress_ratio)
+            .max_decompressed_bytes(cfg.max_decompressed_bytes);
         Either::Left(fut.err_into())
     }
 }
@@ -148,6 +157,10 @@ impl FromRequest for String {
         };
         let limit = cfg.limit;
         let body_fut = HttpMessageBody::new(req, payload).limit(limit);
+        #[cfg(feature = "compress")]
+        let body_fut = body_fut
+            .max_decompress_ratio(cfg.max_decompress_ratio)
+            .max_decompressed_bytes(cfg.max_decompressed_bytes);
 
         Either::Left(StringExtractFut { body_fut, encoding })
     }
@@ -197,7 +210,13 @@ fn bytes_to_string(body: Bytes, encoding: &'static Encoding) -> Result<String, E
 #[derive(Clone)]
 pub struct PayloadConfig {
     limit: usize,
-    mimetype: Option<Mime>,
+    mime_matchers: Vec<MimeMatcher>,
+    spool_threshold: usize,
+    spool_dir: Option<PathBuf>,
+    #[cfg(feature = "compress")]
+    max_decompress_ratio: usize,
+    #[cfg(feature = "compress")]
+    max_decompressed_bytes: Option<usize>,
 }
 
 impl PayloadConfig {
@@ -216,17 +235,64 @@ impl PayloadConfig {
     }
 
     /// Set required mime type of the request. By default mime type is not enforced.
+    ///
+    /// Exact-match shorthand for [`accept_mime`](Self::accept_mime); replaces any previously
+    /// accepted mime types. Unlike `accept_mime`, this is a strict equality check: no wildcard
+    /// or structured syntax suffix matching is applied, preserving the original behavior of
+    /// this method.
     pub fn mimetype(mut self, mt: Mime) -> Self {
-        self.mimetype = Some(mt);
+        self.mime_matchers = vec![MimeMatcher::Exact(mt)];
+        self
+    }
+
+    /// Accept an additional mime type for the request body, on top of any already configured.
+    /// May be called repeatedly to build up a set of accepted types.
+    ///
+    /// Both the type and subtype may be the wildcard `*` (e.g. `text/*`, `*/*`), and an exact
+    /// subtype such as `application/json` also matches structured syntax suffix variants like
+    /// `application/ld+json`.
+    pub fn accept_mime(mut self, mt: Mime) -> Self {
+        self.mime_matchers.push(MimeMatcher::Pattern(mt));
+        self
+    }
+
+    /// Set the in-memory threshold (in bytes) at which [`web::SpooledPayload`] spills the body
+    /// to a temporary file on disk. Has no effect on the [`Bytes`] and [`String`] extractors.
+    /// The default threshold is 256kB.
+    pub fn spool_threshold(mut self, threshold: usize) -> Self {
+        self.spool_threshold = threshold;
+        self
+    }
+
+    /// Set the directory [`web::SpooledPayload`] spills oversized bodies into. Defaults to the
+    /// platform temporary directory.
+    pub fn spool_dir(mut self, dir: PathBuf) -> Self {
+        self.spool_dir = Some(dir);
+        self
+    }
+
+    /// Set the maximum allowed ratio of decompressed to compressed bytes, guarding against
+    /// decompression bombs. The default ratio is 100, i.e. 100 bytes out for every byte in.
+    #[cfg(feature = "compress")]
+    pub fn max_decompress_ratio(mut self, ratio: usize) -> Self {
+        self.max_decompress_ratio = ratio;
+        self
+    }
+
+    /// Set an absolute cap on the number of bytes a payload may decompress to, independent of
+    /// `limit`. By default no separate cap is enforced.
+    #[cfg(feature = "compress")]
+    pub fn max_decompressed_bytes(mut self, max: usize) -> Self {
+        self.max_decompressed_bytes = Some(max);
         self
     }
 
     fn check_mimetype(&self, req: &HttpRequest) -> Result<(), Error> {
         // check content-type
-        if let Some(ref mt) = self.mimetype {
+        if !self.mime_matchers.is_empty() {
             match req.mime_type() {
                 Ok(Some(ref req_mt)) => {
-                    if mt != req_mt {
+                    if !self.mime_matchers.iter().any(|m| m.matches(req_mt)) {
                         return Err(ErrorBadRequest("Unexpected Content-Type"));
                     }
                 }
@@ -253,17 +319,87 @@ impl PayloadConfig {
 /// Allow shared refs used as defaults.
 const DEFAULT_CONFIG: PayloadConfig = PayloadConfig {
     limit: DEFAULT_CONFIG_LIMIT,
-    mimetype: None,
+    mime_matchers: Vec::new(),
+    spool_threshold: DEFAULT_CONFIG_LIMIT,
+    spool_dir: None,
+    #[cfg(feature = "compress")]
+    max_decompress_ratio: DEFAULT_MAX_DECOMPRESS_RATIO,
+    #[cfg(feature = "compress")]
+    max_decompressed_bytes: None,
 };
 
 const DEFAULT_CONFIG_LIMIT: usize = 262_144; // 2^18 bytes (~256kB)
 
+#[cfg(feature = "compress")]
+const DEFAULT_MAX_DECOMPRESS_RATIO: usize = 100;
+
+/// A configured mime type condition, as set via [`PayloadConfig::mimetype`] or
+/// [`PayloadConfig::accept_mime`].
+#[derive(Clone)]
+enum MimeMatcher {
+    /// Set by `mimetype()`. Strict equality, preserving that method's original
+    /// backward-compatible contract.
+    Exact(Mime),
+    /// Set by `accept_mime()`. Honors wildcard type/subtype components (e.g. `text/*`,
+    /// `*/*`) as well as structured syntax suffixes: a pattern of `application/json` also
+    /// matches suffixed variants such as `application/ld+json`.
+    Pattern(Mime),
+}
+
+impl MimeMatcher {
+    fn matches(&self, mt: &Mime) -> bool {
+        match self {
+            MimeMatcher::Exact(pattern) => pattern == mt,
+            MimeMatcher::Pattern(pattern) => {
+                if pattern.type_() != mime::STAR && pattern.type_() != mt.type_() {
+                    return false;
+                }
+
+                // a suffixed pattern such as `application/*+json` requires the same suffix
+                if let Some(suffix) = pattern.suffix() {
+                    if mt.suffix().map_or(true, |mt_suffix| mt_suffix != suffix) {
+                        return false;
+                    }
+                }
+
+                pattern.subtype() == mime::STAR
+                    || pattern.subtype() == mt.subtype()
+                    || mt
+                        .suffix()
+                        .map_or(false, |suffix| suffix == pattern.subtype())
+            }
+        }
+    }
+}
+
 impl Default for PayloadConfig {
     fn default() -> Self {
         DEFAULT_CONFIG.clone()
     }
 }
 
+/// Wraps a payload stream and counts the bytes read from it, so that the compressed size of a
+/// request can be observed alongside the decompressed size produced by `dev::Decompress`.
+#[cfg(feature = "compress")]
+struct CountingPayload {
+    inner: dev::Payload,
+    counter: Rc<Cell<usize>>,
+}
+
+#[cfg(feature = "compress")]
+impl Stream for CountingPayload {
+    type Item = Result<Bytes, PayloadError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let res = ready!(Pin::new(&mut this.inner).poll_next(cx));
+        if let Some(Ok(ref chunk)) = res {
+            this.counter.set(this.counter.get() + chunk.len());
+        }
+        Poll::Ready(res)
+    }
+}
+
 /// Future that resolves to a complete HTTP body payload.
 ///
 /// By default only 256kB payload is accepted before `PayloadError::Overflow` is returned.
@@ -272,11 +408,17 @@ pub struct HttpMessageBody {
     limit: usize,
     length: Option<usize>,
     #[cfg(feature = "compress")]
-    stream: dev::Decompress<dev::Payload>,
+    stream: dev::Decompress<CountingPayload>,
     #[cfg(not(feature = "compress"))]
     stream: dev::Payload,
     buf: BytesMut,
     err: Option<PayloadError>,
+    #[cfg(feature = "compress")]
+    compressed_bytes: Rc<Cell<usize>>,
+    #[cfg(feature = "compress")]
+    max_decompress_ratio: usize,
+    #[cfg(feature = "compress")]
+    max_decompressed_bytes: Option<usize>,
 }
 
 impl HttpMessageBody {
@@ -289,12 +431,9 @@ impl HttpMessageBody {
         if let Some(l) = req.headers().get(&header::CONTENT_LENGTH) {
             match l.to_str() {
                 Ok(s) => match s.parse::<usize>() {
-                    Ok(l) => {
-                        if l > DEFAULT_CONFIG_LIMIT {
-                            err = Some(PayloadError::Overflow);
-                        }
-                        length = Some(l)
-                    }
+                    // Overflow is decided against the *configured* limit, not a hardcoded
+                    // default, so it's deferred to `limit()` below.
+                    Ok(l) => length = Some(l),
                     Err(_) => err = Some(PayloadError::UnknownLength),
                 },
                 Err(_) => err = Some(PayloadError::UnknownLength),
@@ -302,7 +441,15 @@ impl HttpMessageBody {
         }
 
         #[cfg(feature = "compress")]
-        let stream = dev::Decompress::from_headers(payload.take(), req.headers());
+        let compressed_bytes = Rc::new(Cell::new(0));
+        #[cfg(feature = "compress")]
+        let stream = dev::Decompress::from_headers(
+            CountingPayload {
+                inner: payload.take(),
+                counter: Rc::clone(&compressed_bytes),
+            },
+            req.headers(),
+        );
         #[cfg(not(feature = "compress"))]
         let stream = payload.take();
 
@@ -312,6 +459,12 @@ impl HttpMessageBody {
             length,
             buf: BytesMut::with_capacity(8192),
             err,
+            #[cfg(feature = "compress")]
+            compressed_bytes,
+            #[cfg(feature = "compress")]
+            max_decompress_ratio: DEFAULT_MAX_DECOMPRESS_RATIO,
+            #[cfg(feature = "compress")]
+            max_decompressed_bytes: None,
         }
     }
 
@@ -327,6 +480,22 @@ impl HttpMessageBody {
         self.limit = limit;
         self
     }
+
+    /// Change the maximum allowed ratio of decompressed to compressed bytes. By default the
+    /// ratio is 100.
+    #[cfg(feature = "compress")]
+    pub fn max_decompress_ratio(mut self, ratio: usize) -> Self {
+        self.max_decompress_ratio = ratio;
+        self
+    }
+
+    /// Change the absolute cap on decompressed payload size, independent of `limit`. By default
+    /// no separate cap is enforced.
+    #[cfg(feature = "compress")]
+    pub fn max_decompressed_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_decompressed_bytes = max;
+        self
+    }
 }
 
 impl Future for HttpMessageBody {
@@ -349,6 +518,27 @@ impl Future for HttpMessageBody {
                     } else {
                         this.buf.extend_from_slice(&chunk);
                     }
+
+                    // `PayloadError` is defined in `actix_http` and can't be extended with a
+                    // dedicated variant from here, so a ratio/absolute-cap violation is
+                    // reported the same way an in-memory limit violation is: `Overflow`.
+                    #[cfg(feature = "compress")]
+                    {
+                        let consumed = this.compressed_bytes.get();
+                        let emitted = this.buf.len();
+
+                        if consumed > 0
+                            && emitted > consumed.saturating_mul(this.max_decompress_ratio)
+                        {
+                            return Poll::Ready(Err(PayloadError::Overflow));
+                        }
+
+                        if let Some(max) = this.max_decompressed_bytes {
+                            if emitted > max {
+                                return Poll::Ready(Err(PayloadError::Overflow));
+                            }
+                        }
+                    }
                 }
                 None => return Poll::Ready(Ok(this.buf.split().freeze())),
             }
@@ -356,6 +546,280 @@ impl Future for HttpMessageBody {
     }
 }
 
+/// Extract a request's payload into a temporary file, buffering it in memory only until it
+/// grows past an in-memory threshold.
+///
+/// Useful for large uploads (file ingest, media) where buffering the whole body in memory is
+/// undesirable. While reading, up to [`PayloadConfig::spool_threshold`] bytes are buffered in
+/// memory; once that is exceeded the buffer and every later chunk are streamed straight to a
+/// temporary file under [`PayloadConfig::spool_dir`], still honoring [`PayloadConfig::limit`]
+/// as a hard cap. A body that never exceeds the threshold is written to a temporary file once
+/// it is complete, so the extracted payload is always backed by a file.
+///
+/// # Usage
+/// ```
+/// use actix_web::{post, web};
+///
+/// #[post("/upload")]
+/// async fn upload(body: web::SpooledPayload) -> String {
+///     format!("received {} bytes", body.len())
+/// }
+/// ```
+pub struct SpooledPayload {
+    file: File,
+    length: u64,
+}
+
+impl SpooledPayload {
+    /// Total length of the payload, in bytes.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns `true` if the payload is empty.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Unwrap into the spooled file, seeked back to the start.
+    pub fn into_file(mut self) -> io::Result<File> {
+        self.file.seek(SeekFrom::Start(0))?;
+        Ok(self.file)
+    }
+
+    /// Unwrap into an async handle to the spooled file, seeked back to the start.
+    pub fn into_async_file(self) -> io::Result<tokio::fs::File> {
+        Ok(tokio::fs::File::from_std(self.into_file()?))
+    }
+}
+
+/// See [here](#usage) for example of usage as an extractor.
+impl FromRequest for SpooledPayload {
+    type Config = PayloadConfig;
+    type Error = Error;
+    type Future = Either<ErrInto<SpooledPayloadFut, Error>, Ready<Result<SpooledPayload, Error>>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+        let cfg = PayloadConfig::from_req(req);
+
+        if let Err(err) = cfg.check_mimetype(req) {
+            return Either::Right(ready(Err(err)));
+        }
+
+        Either::Left(SpooledPayloadFut::new(req, payload, cfg).err_into())
+    }
+}
+
+/// A spool write (and, the first time, the temp file creation backing it) running on the
+/// blocking thread pool. Keeps `SpooledPayloadFut::poll` from ever touching `std::fs`
+/// directly, since that would stall the async worker thread for the duration of the write.
+enum SpoolOp {
+    Idle,
+    Writing(tokio::task::JoinHandle<io::Result<File>>),
+}
+
+fn spawn_spool_write(
+    file: Option<File>,
+    spool_dir: Option<PathBuf>,
+    data: Bytes,
+) -> tokio::task::JoinHandle<io::Result<File>> {
+    tokio::task::spawn_blocking(move || {
+        let mut file = match file {
+            Some(file) => file,
+            None => match &spool_dir {
+                Some(dir) => tempfile::tempfile_in(dir)?,
+                None => tempfile::tempfile()?,
+            },
+        };
+        file.write_all(&data)?;
+        Ok(file)
+    })
+}
+
+/// Future that resolves to a [`SpooledPayload`].
+pub struct SpooledPayloadFut {
+    limit: usize,
+    spool_threshold: usize,
+    spool_dir: Option<PathBuf>,
+    length: Option<usize>,
+    #[cfg(feature = "compress")]
+    stream: dev::Decompress<CountingPayload>,
+    #[cfg(not(feature = "compress"))]
+    stream: dev::Payload,
+    buf: BytesMut,
+    file: Option<File>,
+    written: u64,
+    /// `true` once the source stream has yielded its last chunk.
+    done: bool,
+    op: SpoolOp,
+    err: Option<PayloadError>,
+    #[cfg(feature = "compress")]
+    compressed_bytes: Rc<Cell<usize>>,
+    #[cfg(feature = "compress")]
+    max_decompress_ratio: usize,
+    #[cfg(feature = "compress")]
+    max_decompressed_bytes: Option<usize>,
+}
+
+impl SpooledPayloadFut {
+    fn new(req: &HttpRequest, payload: &mut dev::Payload, cfg: &PayloadConfig) -> Self {
+        let mut length = None;
+        let mut err = None;
+
+        if let Some(l) = req.headers().get(&header::CONTENT_LENGTH) {
+            match l.to_str() {
+                Ok(s) => match s.parse::<usize>() {
+                    Ok(l) => {
+                        if l > cfg.limit {
+                            err = Some(PayloadError::Overflow);
+                        }
+                        length = Some(l)
+                    }
+                    Err(_) => err = Some(PayloadError::UnknownLength),
+                },
+                Err(_) => err = Some(PayloadError::UnknownLength),
+            }
+        }
+
+        #[cfg(feature = "compress")]
+        let compressed_bytes = Rc::new(Cell::new(0));
+        #[cfg(feature = "compress")]
+        let stream = dev::Decompress::from_headers(
+            CountingPayload {
+                inner: payload.take(),
+                counter: Rc::clone(&compressed_bytes),
+            },
+            req.headers(),
+        );
+        #[cfg(not(feature = "compress"))]
+        let stream = payload.take();
+
+        SpooledPayloadFut {
+            stream,
+            limit: cfg.limit,
+            spool_threshold: cfg.spool_threshold,
+            spool_dir: cfg.spool_dir.clone(),
+            length,
+            buf: BytesMut::with_capacity(8192),
+            file: None,
+            written: 0,
+            done: false,
+            op: SpoolOp::Idle,
+            err,
+            #[cfg(feature = "compress")]
+            compressed_bytes,
+            #[cfg(feature = "compress")]
+            max_decompress_ratio: cfg.max_decompress_ratio,
+            #[cfg(feature = "compress")]
+            max_decompressed_bytes: cfg.max_decompressed_bytes,
+        }
+    }
+}
+
+impl Future for SpooledPayloadFut {
+    type Output = Result<SpooledPayload, PayloadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if let Some(err) = this.err.take() {
+            return Poll::Ready(Err(err));
+        }
+
+        loop {
+            if let SpoolOp::Writing(handle) = &mut this.op {
+                let file = ready!(Pin::new(handle).poll(cx))
+                    .map_err(|_| {
+                        PayloadError::Io(io::Error::new(
+                            io::ErrorKind::Other,
+                            "spool write task panicked",
+                        ))
+                    })?
+                    .map_err(PayloadError::Io)?;
+                this.file = Some(file);
+                this.op = SpoolOp::Idle;
+                continue;
+            }
+
+            if this.done {
+                let file = this.file.take().expect("body is always spooled to a file");
+                return Poll::Ready(Ok(SpooledPayload {
+                    file,
+                    length: this.written,
+                }));
+            }
+
+            let res = ready!(Pin::new(&mut this.stream).poll_next(cx));
+            match res {
+                Some(chunk) => {
+                    let chunk = chunk?;
+
+                    let prospective_len =
+                        this.written + this.buf.len() as u64 + chunk.len() as u64;
+                    if prospective_len > this.limit as u64 {
+                        return Poll::Ready(Err(PayloadError::Overflow));
+                    }
+
+                    // Spooling lets callers raise `limit` far past the in-memory default for
+                    // legitimate large uploads, so without this check a small compressed body
+                    // could otherwise decompress straight to disk up to that much higher limit.
+                    // See the matching check in `HttpMessageBody::poll` for why `Overflow` is
+                    // reused here instead of a dedicated variant.
+                    #[cfg(feature = "compress")]
+                    {
+                        let consumed = this.compressed_bytes.get();
+                        let emitted = prospective_len as usize;
+
+                        if consumed > 0
+                            && emitted > consumed.saturating_mul(this.max_decompress_ratio)
+                        {
+                            return Poll::Ready(Err(PayloadError::Overflow));
+                        }
+
+                        if let Some(max) = this.max_decompressed_bytes {
+                            if emitted > max {
+                                return Poll::Ready(Err(PayloadError::Overflow));
+                            }
+                        }
+                    }
+
+                    if this.file.is_some() {
+                        this.written += chunk.len() as u64;
+                        this.op = SpoolOp::Writing(spawn_spool_write(
+                            this.file.take(),
+                            this.spool_dir.clone(),
+                            chunk,
+                        ));
+                    } else if this.buf.len() + chunk.len() > this.spool_threshold {
+                        let mut buffered = this.buf.split();
+                        buffered.extend_from_slice(&chunk);
+                        let data = buffered.freeze();
+                        this.written += data.len() as u64;
+                        this.op =
+                            SpoolOp::Writing(spawn_spool_write(None, this.spool_dir.clone(), data));
+                    } else {
+                        this.buf.extend_from_slice(&chunk);
+                    }
+                }
+                None => {
+                    this.done = true;
+
+                    if !this.buf.is_empty() || this.file.is_none() {
+                        let data = this.buf.split().freeze();
+                        this.written += data.len() as u64;
+                        this.op = SpoolOp::Writing(spawn_spool_write(
+                            this.file.take(),
+                            this.spool_dir.clone(),
+                            data,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -382,6 +846,58 @@ mod tests {
         assert!(cfg.check_mimetype(&req).is_ok());
     }
 
+    #[actix_rt::test]
+    async fn test_mimetype_is_strict_equality() {
+        // `mimetype()` predates `accept_mime()`'s wildcard/suffix matching and must keep its
+        // original strict-equality contract: it should NOT also accept structured syntax
+        // suffix variants the way `accept_mime()` does.
+        let cfg = PayloadConfig::default().mimetype(mime::APPLICATION_JSON);
+
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/ld+json"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_err());
+
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_payload_config_accept_mime() {
+        let cfg = PayloadConfig::default()
+            .accept_mime(mime::TEXT_STAR)
+            .accept_mime(mime::APPLICATION_JSON);
+
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "text/plain"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_ok());
+
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "text/html"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_ok());
+
+        // exact match
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_ok());
+
+        // structured syntax suffix match
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/ld+json"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_ok());
+
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/xml"))
+            .to_http_request();
+        assert!(cfg.check_mimetype(&req).is_err());
+    }
+
     #[actix_rt::test]
     async fn test_config_recall_locations() {
         async fn bytes_handler(_: Bytes) -> impl Responder {
@@ -500,7 +1016,9 @@ mod tests {
             .insert_header((header::CONTENT_LENGTH, "1000000"))
             .to_srv_request()
             .into_parts();
-        let res = HttpMessageBody::new(&req, &mut pl).await;
+        let res = HttpMessageBody::new(&req, &mut pl)
+            .limit(DEFAULT_CONFIG_LIMIT)
+            .await;
         match res.err().unwrap() {
             PayloadError::Overflow => {}
             _ => unreachable!("error"),
@@ -521,4 +1039,124 @@ mod tests {
             _ => unreachable!("error"),
         }
     }
+
+    #[actix_rt::test]
+    async fn test_content_length_precheck_deferred_to_limit() {
+        // `Bytes`/`String::from_request` always chain `.limit(cfg.limit)` straight after
+        // `new()`, so this only changes behavior for callers who use `HttpMessageBody::new`
+        // directly and never call `.limit()` at all: `new()` must not pre-decide Overflow
+        // against the hardcoded default, since the struct's own default limit field (not a
+        // construction-time decision) is what ends up enforcing it once the body is polled.
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_LENGTH, "1048576"))
+            .to_srv_request()
+            .into_parts();
+        let body = HttpMessageBody::new(&req, &mut pl);
+        assert!(body.err.is_none());
+
+        // calling `.limit()` still re-derives the decision from the declared length, as it
+        // always has.
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_LENGTH, "1048576"))
+            .to_srv_request()
+            .into_parts();
+        let res = HttpMessageBody::new(&req, &mut pl)
+            .limit(DEFAULT_CONFIG_LIMIT)
+            .await;
+        match res.err().unwrap() {
+            PayloadError::Overflow => {}
+            _ => unreachable!("error"),
+        }
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_LENGTH, "1048576"))
+            .to_srv_request()
+            .into_parts();
+        let res = HttpMessageBody::new(&req, &mut pl).limit(10 * 1024 * 1024);
+        assert!(res.err.is_none());
+    }
+
+    #[cfg(feature = "compress")]
+    #[actix_rt::test]
+    async fn test_decompress_ratio_exceeded() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        // a gzip bomb: a run of zeroes compresses far beyond the default 100:1 ratio
+        let mut enc = GzEncoder::new(Vec::new(), Compression::best());
+        enc.write_all(&vec![0u8; 1_000_000]).unwrap();
+        let compressed = enc.finish().unwrap();
+        assert!(compressed.len() * 100 < 1_000_000);
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_ENCODING, "gzip"))
+            .set_payload(Bytes::from(compressed))
+            .to_http_parts();
+
+        let res = HttpMessageBody::new(&req, &mut pl).limit(10_000_000).await;
+        match res.err().unwrap() {
+            PayloadError::Overflow => {}
+            _ => unreachable!("error"),
+        }
+    }
+
+    #[cfg(feature = "compress")]
+    #[actix_rt::test]
+    async fn test_spooled_payload_decompress_ratio_exceeded() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        // Same gzip bomb as `test_decompress_ratio_exceeded`: spooling raises `limit` far past
+        // the in-memory default for legitimate large uploads, so this must still be caught by
+        // the ratio check rather than being decompressed straight to disk.
+        let mut enc = GzEncoder::new(Vec::new(), Compression::best());
+        enc.write_all(&vec![0u8; 1_000_000]).unwrap();
+        let compressed = enc.finish().unwrap();
+        assert!(compressed.len() * 100 < 1_000_000);
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_ENCODING, "gzip"))
+            .set_payload(Bytes::from(compressed))
+            .to_http_parts();
+
+        let cfg = PayloadConfig::default().limit(10_000_000);
+        let res = SpooledPayloadFut::new(&req, &mut pl, &cfg).await;
+        match res.err().unwrap() {
+            PayloadError::Overflow => {}
+            _ => unreachable!("error"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_spooled_payload() {
+        use std::io::Read;
+
+        let body = Bytes::from_static(b"hello=world");
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_LENGTH, body.len().to_string()))
+            .set_payload(body.clone())
+            .to_http_parts();
+
+        let cfg = PayloadConfig::default().spool_threshold(4);
+        let spooled = SpooledPayloadFut::new(&req, &mut pl, &cfg).await.unwrap();
+        assert_eq!(spooled.len(), body.len() as u64);
+
+        let mut buf = Vec::new();
+        spooled.into_file().unwrap().read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, body.as_ref());
+    }
+
+    #[actix_rt::test]
+    async fn test_spooled_payload_overflow() {
+        let (req, mut pl) = TestRequest::default()
+            .set_payload(Bytes::from_static(b"11111111111111"))
+            .to_http_parts();
+
+        let cfg = PayloadConfig::default().limit(5).spool_threshold(2);
+        let res = SpooledPayloadFut::new(&req, &mut pl, &cfg).await;
+        match res.err().unwrap() {
+            PayloadError::Overflow => {}
+            _ => unreachable!("error"),
+        }
+    }
 }